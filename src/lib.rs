@@ -2,45 +2,171 @@
  * The filesystem as modelled by 9P2000
  */
 
+use std::fmt;
 use std::io::{Read, Write};
 use std::convert::{TryFrom, TryInto};
 
-pub struct Vfs9Error();
+const EPERM: i32  = 1;
+const ENOENT: i32 = 2;
+const EEXIST: i32 = 17;
+const EISDIR: i32 = 21;
+const EINVAL: i32 = 22;
+
+/// A 9P error: the human-readable message a classic `Rerror` sends, plus an
+/// optional numeric errno for the 9P2000.u/.L dialects (which extend `Rerror`
+/// with one). Every failure path in this crate — permission denied, a bad
+/// flag combination, no such file, wstat on a directory — produces one of
+/// these rather than an opaque unit value, so a server loop can report a
+/// faithful `Rerror`.
+#[derive(Debug, PartialEq)]
+pub struct Vfs9Error {
+    message: String,
+    errno: Option<i32>,
+}
 
-type Result<T> = std::result::Result<T, Vfs9Error>;
+impl Vfs9Error {
+    pub fn new(message: impl Into<String>, errno: Option<i32>) -> Self {
+        Self { message: message.into(), errno }
+    }
 
-#[derive(Debug, PartialEq)]
-pub struct FileType {
-    pub is_dir: bool,
-    pub is_append_only: bool,
-    pub is_exclusive: bool,
-    pub is_auth: bool,
-    pub is_temporary: bool,
+    /// The message a classic `Rerror` would carry.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The 9P2000.u/.L numeric errno, if one applies.
+    pub fn errno(&self) -> Option<i32> {
+        self.errno
+    }
+
+    pub fn perm() -> Self {
+        Self::new("permission denied", Some(EPERM))
+    }
+
+    pub fn not_found() -> Self {
+        Self::new("file does not exist", Some(ENOENT))
+    }
+
+    pub fn exists() -> Self {
+        Self::new("file already exists", Some(EEXIST))
+    }
+
+    pub fn is_dir() -> Self {
+        Self::new("wstat -- not a file", Some(EISDIR))
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(message, Some(EINVAL))
+    }
+}
+
+impl fmt::Display for Vfs9Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
 }
 
+impl std::error::Error for Vfs9Error {}
+
+type Result<T> = std::result::Result<T, Vfs9Error>;
+
+/// The type bits of a qid / `FileMode`, as a validated set of flags rather
+/// than a hand-decoded `u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileType(u8);
+
 impl FileType {
-    pub fn from_bits(b: u8) -> Self {
-        Self {
-            // bit 28 is skipped for 'historical reasons'
-            is_dir:         b & 0b10000000 != 0,
-            is_append_only: b & 0b01000000 != 0,
-            is_exclusive:   b & 0b00100000 != 0,
-            is_auth:        b & 0b00001000 != 0,
-            is_temporary:   b & 0b00000100 != 0
+    pub const DIR: Self         = Self(0b10000000);
+    pub const APPEND_ONLY: Self = Self(0b01000000);
+    pub const EXCLUSIVE: Self   = Self(0b00100000);
+    pub const AUTH: Self        = Self(0b00001000);
+    pub const TEMPORARY: Self   = Self(0b00000100);
+
+    const KNOWN_BITS: u8 =
+        Self::DIR.0 | Self::APPEND_ONLY.0 | Self::EXCLUSIVE.0 | Self::AUTH.0 | Self::TEMPORARY.0;
+
+    /// Bit 0b00010000 (bit 28 of the full 32-bit mode word) carries no
+    /// meaning of its own but is set by real servers for historical reasons,
+    /// so `from_bits` ignores it instead of rejecting it as unknown.
+    const IGNORED_BITS: u8 = 0b00010000;
+
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Validates `b`, rejecting any set bit that isn't one of the known
+    /// flags above (or the historically-ignored bit).
+    pub fn from_bits(b: u8) -> Option<Self> {
+        if b & !(Self::KNOWN_BITS | Self::IGNORED_BITS) != 0 {
+            return None;
         }
+
+        Some(Self(b & Self::KNOWN_BITS))
+    }
+
+    /// Like `from_bits`, but skips the unknown-bit check and silently drops
+    /// any bit that isn't a known flag. Intended for data that is already
+    /// trusted, such as bits freshly read off the wire.
+    ///
+    /// # Safety
+    /// `b` must only set bits that are valid for this type; passing bits
+    /// that were not already validated (e.g. by `from_bits`, or by having
+    /// come straight off the wire) may silently produce a `FileType` that
+    /// doesn't reflect the caller's intent.
+    pub unsafe fn from_bits_unchecked(b: u8) -> Self {
+        Self(b & Self::KNOWN_BITS)
     }
 
     pub fn to_bits(&self) -> u8 {
-        let mut b = 0x00;
+        self.0
+    }
 
-        // bit 28 is skipped for 'historical reasons'
-        if self.is_dir         { b |= 0b10000000; }
-        if self.is_append_only { b |= 0b01000000; }
-        if self.is_exclusive   { b |= 0b00100000; }
-        if self.is_auth        { b |= 0b00001000; }
-        if self.is_temporary   { b |= 0b00000100; }
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
 
-        b
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.contains(Self::DIR)
+    }
+
+    pub fn is_append_only(&self) -> bool {
+        self.contains(Self::APPEND_ONLY)
+    }
+
+    pub fn is_exclusive(&self) -> bool {
+        self.contains(Self::EXCLUSIVE)
+    }
+
+    pub fn is_auth(&self) -> bool {
+        self.contains(Self::AUTH)
+    }
+
+    pub fn is_temporary(&self) -> bool {
+        self.contains(Self::TEMPORARY)
+    }
+}
+
+impl std::ops::BitOr for FileType {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for FileType {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
     }
 }
 
@@ -61,6 +187,84 @@ pub struct Qid {
     pub path: u64
 }
 
+// 9P's machine-independent wire encoding: all integers are little-endian, and
+// strings are a 2-byte little-endian length followed by UTF-8 bytes (no NUL
+// terminator). These helpers are shared by `Qid::encode`/`decode`,
+// `FileMode::encode`/`decode` and `Stat::encode`/`decode`.
+
+fn io_err(e: std::io::Error) -> Vfs9Error {
+    Vfs9Error::new(format!("i/o error: {}", e), None)
+}
+
+fn write_u16(w: &mut dyn Write, v: u16) -> Result<()> {
+    w.write_all(&v.to_le_bytes()).map_err(io_err)
+}
+
+fn write_u32(w: &mut dyn Write, v: u32) -> Result<()> {
+    w.write_all(&v.to_le_bytes()).map_err(io_err)
+}
+
+fn write_u64(w: &mut dyn Write, v: u64) -> Result<()> {
+    w.write_all(&v.to_le_bytes()).map_err(io_err)
+}
+
+fn read_u16(r: &mut dyn Read) -> Result<u16> {
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf).map_err(io_err)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32(r: &mut dyn Read) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf).map_err(io_err)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(r: &mut dyn Read) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf).map_err(io_err)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn write_string(w: &mut dyn Write, s: &str) -> Result<()> {
+    let bytes = s.as_bytes();
+    if bytes.len() > u16::MAX as usize {
+        return Err(Vfs9Error::invalid("string too long to encode on the wire"));
+    }
+    write_u16(w, bytes.len() as u16)?;
+    w.write_all(bytes).map_err(io_err)
+}
+
+fn read_string(r: &mut dyn Read) -> Result<String> {
+    let len = read_u16(r)? as usize;
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf).map_err(io_err)?;
+    String::from_utf8(buf).map_err(|e| Vfs9Error::invalid(format!("invalid utf-8 in wire string: {}", e)))
+}
+
+impl Qid {
+    /// Encodes this qid as the fixed 13 bytes it occupies on the wire:
+    /// a 1-byte type, a 4-byte version and an 8-byte path, all little-endian.
+    pub fn encode(&self, w: &mut dyn Write) -> Result<()> {
+        w.write_all(&[self.file_type.to_bits()]).map_err(io_err)?;
+        write_u32(w, self.version)?;
+        write_u64(w, self.path)
+    }
+
+    pub fn decode(r: &mut dyn Read) -> Result<Self> {
+        let mut type_byte = [0u8; 1];
+        r.read_exact(&mut type_byte).map_err(io_err)?;
+        let version = read_u32(r)?;
+        let path = read_u64(r)?;
+
+        Ok(Self {
+            file_type: unsafe { FileType::from_bits_unchecked(type_byte[0]) },
+            version,
+            path
+        })
+    }
+}
+
 /// The IoUnit field is the maximum number of bytes that are guaranteed to be read from or written to a given file,
 /// without breaking the I/O transfer into multiple 9P messages; see read(5).
 pub type IoUnit = u32;
@@ -83,41 +287,102 @@ impl TryFrom<u8> for OpenSubMode {
             1 => Ok(Self::Write),
             2 => Ok(Self::ReadWrite),
             3 => Ok(Self::Execute),
-            _ => Err(Vfs9Error())
+            _ => Err(Vfs9Error::invalid(format!("invalid open submode bits: {:#04b}", mode)))
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct OpenMode {
-    pub submode: OpenSubMode,
+/// Classic 9P2000's open mode word: a 2-bit submode plus the independent
+/// truncate/rclose flags, validated as a typed bit set rather than decoded
+/// by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenMode(u8);
 
+impl OpenMode {
     /// if mode has the OTRUNC (0x10) bit set, the file is to be truncated,
     /// which requires write permission (if the file is append-only, and permission is granted,
     /// the open succeeds but the file will not be trun- cated)
-    pub truncate: bool,
+    pub const TRUNCATE: Self = Self(0b00010000);
 
     /// if the mode has the ORCLOSE (0x40) bit set,
     /// the file is to be removed when the fid is clunked,
     /// which requires permission to remove the file from its directory.
-    pub rclose: bool,
-}
+    pub const RCLOSE: Self = Self(0b01000000);
 
-impl OpenMode {
+    const SUBMODE_MASK: u8 = 0b00000011;
+    const KNOWN_BITS: u8 = Self::TRUNCATE.0 | Self::RCLOSE.0 | Self::SUBMODE_MASK;
+
+    /// Validates `fields`, rejecting both an out-of-range submode and any
+    /// set bit outside `TRUNCATE`/`RCLOSE`/the submode bits.
     pub fn from_bits(fields: u8) -> Result<Self> {
-        let mut s = Self {
-            submode: fields.try_into()?,
-            truncate: false,
-            rclose: false
-        };
+        if fields & !Self::KNOWN_BITS != 0 {
+            return Err(Vfs9Error::invalid(format!("invalid open mode bits: {:#010b}", fields)));
+        }
 
-        if fields & 0b00010000 != 0 { s.truncate = true; } // =0x10
-        if fields & 0b01000000 != 0 { s.rclose = true; }   // =0x40
+        let _: OpenSubMode = (fields & Self::SUBMODE_MASK).try_into()?;
 
-        Ok(s)
+        Ok(Self(fields))
+    }
+
+    /// Like `from_bits`, but skips the unknown-bit and submode-range checks.
+    /// Intended for data that is already trusted, such as bits freshly read
+    /// off the wire.
+    ///
+    /// # Safety
+    /// `fields` must only set bits that are valid for this type and encode a
+    /// valid submode; passing unvalidated bits may silently produce an
+    /// `OpenMode` that doesn't reflect the caller's intent.
+    pub unsafe fn from_bits_unchecked(fields: u8) -> Self {
+        Self(fields & Self::KNOWN_BITS)
+    }
+
+    pub fn to_bits(&self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    pub fn submode(&self) -> OpenSubMode {
+        (self.0 & Self::SUBMODE_MASK).try_into()
+            .expect("submode bits were validated when this OpenMode was constructed")
+    }
+
+    pub fn truncate(&self) -> bool {
+        self.contains(Self::TRUNCATE)
+    }
+
+    pub fn rclose(&self) -> bool {
+        self.contains(Self::RCLOSE)
+    }
+}
+
+impl std::ops::BitOr for OpenMode {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for OpenMode {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
     }
 }
 
+/// A thin, read-only view of one rwx triple of a `Permissions` value.
 #[derive(Debug, PartialEq)]
 pub struct IndividualPermissions {
     pub read: bool,
@@ -125,14 +390,6 @@ pub struct IndividualPermissions {
     pub execute: bool,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Permissions {
-    pub owner: IndividualPermissions,
-    pub group: IndividualPermissions,
-    pub other: IndividualPermissions,
-}
-
-
 const BIT_OTHER_EXECUTE: u32  = 0b00000000000000000000000000000001;
 const BIT_OTHER_WRITE: u32    = 0b00000000000000000000000000000010;
 const BIT_OTHER_READ: u32     = 0b00000000000000000000000000000100;
@@ -145,66 +402,225 @@ const BIT_OWNER_EXECUTE: u32 = BIT_GROUP_EXECUTE << 3;
 const BIT_OWNER_WRITE: u32   = BIT_GROUP_WRITE << 3;
 const BIT_OWNER_READ: u32    = BIT_GROUP_READ << 3;
 
-impl Permissions {
-    pub fn from_bits(fields: u32) -> Self {
-        let mut p = Self {
-            owner: IndividualPermissions { read: false, write: false, execute: false },
-            group: IndividualPermissions { read: false, write: false, execute: false },
-            other: IndividualPermissions { read: false, write: false, execute: false }
-        };
+/// The rwxrwxrwx permission bits of a `FileMode`, as a validated set of
+/// flags rather than a hand-decoded `u32`. `owner`/`group`/`other` remain
+/// available as thin accessors returning an `IndividualPermissions`, so a
+/// single triple's bits can still be read the way the unpacked struct fields
+/// used to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permissions(u32);
 
-        //           0b00000000000000000000000000000000: 32 bit integer
-        if (fields & BIT_OTHER_EXECUTE) != 0 { p.other.execute = true; }
-        if (fields & BIT_OTHER_WRITE)   != 0 { p.other.write = true; }
-        if (fields & BIT_OTHER_READ)    != 0 { p.other.read = true; }
+impl Permissions {
+    pub const OWNER_READ: Self    = Self(BIT_OWNER_READ);
+    pub const OWNER_WRITE: Self   = Self(BIT_OWNER_WRITE);
+    pub const OWNER_EXECUTE: Self = Self(BIT_OWNER_EXECUTE);
+    pub const GROUP_READ: Self    = Self(BIT_GROUP_READ);
+    pub const GROUP_WRITE: Self   = Self(BIT_GROUP_WRITE);
+    pub const GROUP_EXECUTE: Self = Self(BIT_GROUP_EXECUTE);
+    pub const OTHER_READ: Self    = Self(BIT_OTHER_READ);
+    pub const OTHER_WRITE: Self   = Self(BIT_OTHER_WRITE);
+    pub const OTHER_EXECUTE: Self = Self(BIT_OTHER_EXECUTE);
+
+    const KNOWN_BITS: u32 =
+        Self::OWNER_READ.0 | Self::OWNER_WRITE.0 | Self::OWNER_EXECUTE.0 |
+        Self::GROUP_READ.0 | Self::GROUP_WRITE.0 | Self::GROUP_EXECUTE.0 |
+        Self::OTHER_READ.0 | Self::OTHER_WRITE.0 | Self::OTHER_EXECUTE.0;
+
+    pub fn empty() -> Self {
+        Self(0)
+    }
 
-        if (fields & BIT_GROUP_EXECUTE) != 0 { p.group.execute = true; }
-        if (fields & BIT_GROUP_WRITE)   != 0 { p.group.write = true; }
-        if (fields & BIT_GROUP_READ)    != 0 { p.group.read = true; }
+    /// Validates `fields`, rejecting any set bit outside the nine rwx bits
+    /// above.
+    pub fn from_bits(fields: u32) -> Option<Self> {
+        if fields & !Self::KNOWN_BITS != 0 {
+            return None;
+        }
 
-        if (fields & BIT_OWNER_EXECUTE) != 0 { p.owner.execute = true; }
-        if (fields & BIT_OWNER_WRITE)   != 0 { p.owner.write = true; }
-        if (fields & BIT_OWNER_READ)    != 0 { p.owner.read = true; }
+        Some(Self(fields))
+    }
 
-        p
+    /// Like `from_bits`, but skips the unknown-bit check and silently drops
+    /// any bit that isn't a known permission. Intended for data that is
+    /// already trusted, such as bits freshly read off the wire.
+    ///
+    /// # Safety
+    /// `fields` must only set bits that are valid for this type; passing
+    /// unvalidated bits may silently produce a `Permissions` that doesn't
+    /// reflect the caller's intent.
+    pub unsafe fn from_bits_unchecked(fields: u32) -> Self {
+        Self(fields & Self::KNOWN_BITS)
     }
 
     pub fn to_bits(&self) -> u32 {
-        let mut b: u32 = 0x00000000;
+        self.0
+    }
 
-        if self.other.execute { b |= BIT_OTHER_EXECUTE; }
-        if self.other.write   { b |= BIT_OTHER_WRITE;   }
-        if self.other.read    { b |= BIT_OTHER_READ;    }
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
 
-        if self.group.execute { b |= BIT_GROUP_EXECUTE; }
-        if self.group.write   { b |= BIT_GROUP_WRITE;   }
-        if self.group.read    { b |= BIT_GROUP_READ;    }
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
 
-        if self.owner.execute { b |= BIT_OWNER_EXECUTE; }
-        if self.owner.write   { b |= BIT_OWNER_WRITE;   }
-        if self.owner.read    { b |= BIT_OWNER_READ;    }
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
 
-        b
+    pub fn owner(&self) -> IndividualPermissions {
+        IndividualPermissions {
+            read: self.contains(Self::OWNER_READ),
+            write: self.contains(Self::OWNER_WRITE),
+            execute: self.contains(Self::OWNER_EXECUTE),
+        }
+    }
+
+    pub fn group(&self) -> IndividualPermissions {
+        IndividualPermissions {
+            read: self.contains(Self::GROUP_READ),
+            write: self.contains(Self::GROUP_WRITE),
+            execute: self.contains(Self::GROUP_EXECUTE),
+        }
+    }
+
+    pub fn other(&self) -> IndividualPermissions {
+        IndividualPermissions {
+            read: self.contains(Self::OTHER_READ),
+            write: self.contains(Self::OTHER_WRITE),
+            execute: self.contains(Self::OTHER_EXECUTE),
+        }
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl std::ops::BitOr for Permissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for Permissions {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::Not for Permissions {
+    type Output = Self;
+
+    /// Complements within the nine rwx bits, so e.g. the 9P `create`
+    /// permission mask `perm & (~0666 | (dir.perm & 0666))` can be computed
+    /// directly on `Permissions` values instead of dropping back to `u32`.
+    fn not(self) -> Self {
+        Self(!self.0 & Self::KNOWN_BITS)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FileMode {
     pub permissions: Permissions,
     pub file_type: FileType,
 }
 
 impl FileMode {
-    pub fn from_bits(fields: u32) -> Self {
+    pub fn from_bits(fields: u32) -> Result<Self> {
+        let known_bits = Permissions::KNOWN_BITS | ((FileType::KNOWN_BITS | FileType::IGNORED_BITS) as u32) << 24;
+
+        if fields & !known_bits != 0 {
+            return Err(Vfs9Error::invalid(format!("invalid file mode bits: {:#010x}", fields)));
+        }
+
+        // Safety: every bit of `fields` was just checked against `known_bits`, which is
+        // exactly the union of what `Permissions::from_bits`/`FileType::from_bits` accept.
+        Ok(unsafe { Self::from_bits_unchecked(fields) })
+    }
+
+    /// Like `from_bits`, but skips validation of both the permission and
+    /// file type bits. Intended for data that is already trusted, such as
+    /// bits freshly read off the wire.
+    ///
+    /// # Safety
+    /// `fields` must only set permission/file-type bits that are valid; see
+    /// `Permissions::from_bits_unchecked` and `FileType::from_bits_unchecked`.
+    pub unsafe fn from_bits_unchecked(fields: u32) -> Self {
         Self {
-            permissions: Permissions::from_bits(fields),
-            file_type: FileType::from_bits((fields >> 24) as u8)
+            permissions: Permissions::from_bits_unchecked(fields),
+            file_type: FileType::from_bits_unchecked((fields >> 24) as u8),
         }
     }
 
     pub fn to_bits(&self) -> u32 {
         self.permissions.to_bits() | (self.file_type.to_bits() as u32) << 24
     }
+
+    /// Encodes this mode as the 4-byte little-endian `mode[4]` field of a
+    /// `Stat`: the permission bits plus the `FileType` bits in the top byte.
+    pub fn encode(&self, w: &mut dyn Write) -> Result<()> {
+        write_u32(w, self.to_bits())
+    }
+
+    pub fn decode(r: &mut dyn Read) -> Result<Self> {
+        Ok(unsafe { Self::from_bits_unchecked(read_u32(r)?) })
+    }
+
+    /// The standard Unix `st_mode` rendering of this mode, e.g. `0o100644`
+    /// for a regular file or `0o040755` for a directory: the permission
+    /// bits from `Permissions::to_bits`, with the Unix file-type bits
+    /// (`S_IFDIR`/`S_IFREG`) layered on top instead of this crate's own
+    /// `FileType` bits.
+    pub fn octal(&self) -> u32 {
+        const S_IFDIR: u32 = 0o040000;
+        const S_IFREG: u32 = 0o100000;
+
+        let type_bits = if self.file_type.is_dir() { S_IFDIR } else { S_IFREG };
+
+        type_bits | self.permissions.to_bits()
+    }
+}
+
+impl fmt::Display for Permissions {
+    /// Renders the familiar `rwxrwxrwx` symbolic permission string.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (owner, group, other) = (self.owner(), self.group(), self.other());
+
+        write!(f, "{}{}{}{}{}{}{}{}{}",
+            if owner.read    { 'r' } else { '-' },
+            if owner.write   { 'w' } else { '-' },
+            if owner.execute { 'x' } else { '-' },
+            if group.read    { 'r' } else { '-' },
+            if group.write   { 'w' } else { '-' },
+            if group.execute { 'x' } else { '-' },
+            if other.read    { 'r' } else { '-' },
+            if other.write   { 'w' } else { '-' },
+            if other.execute { 'x' } else { '-' })
+    }
+}
+
+impl fmt::Display for FileMode {
+    /// Renders the symbolic mode string used by standard filesystem
+    /// tooling, e.g. `-rw-rw-r--` for a regular file or `drwxr-xr-x` for a
+    /// directory: a leading type character (`d` directory, `a` append-only,
+    /// `l` exclusive/locked, `t` temporary, `-` otherwise) followed by the
+    /// nine `rwxrwxrwx` permission characters.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let type_char = if self.file_type.is_dir() {
+            'd'
+        } else if self.file_type.is_append_only() {
+            'a'
+        } else if self.file_type.is_exclusive() {
+            'l'
+        } else if self.file_type.is_temporary() {
+            't'
+        } else {
+            '-'
+        };
+
+        write!(f, "{}{}", type_char, self.permissions)
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -242,12 +658,71 @@ pub struct Stat {
     pub muid: String,
 }
 
+impl Stat {
+    /// Encodes this stat the way directory `read` and `wstat` put it on the
+    /// wire: a 2-byte `size[2]` counting all the bytes that follow it, then
+    /// `type[2] dev[4] qid[13] mode[4] atime[4] mtime[4] length[8]` followed
+    /// by the four length-prefixed strings `name uid gid muid`.
+    pub fn encode(&self, w: &mut dyn Write) -> Result<()> {
+        let mut body: Vec<u8> = Vec::new();
+
+        write_u16(&mut body, self.type_)?;
+        write_u32(&mut body, self.dev)?;
+        self.qid.encode(&mut body)?;
+        self.mode.encode(&mut body)?;
+        write_u32(&mut body, self.atime)?;
+        write_u32(&mut body, self.mtime)?;
+        write_u64(&mut body, self.length)?;
+        write_string(&mut body, &self.name)?;
+        write_string(&mut body, &self.uid)?;
+        write_string(&mut body, &self.gid)?;
+        write_string(&mut body, &self.muid)?;
+
+        if body.len() > u16::MAX as usize { return Err(Vfs9Error::invalid("stat too large to encode on the wire")); }
+        write_u16(w, body.len() as u16)?;
+        w.write_all(&body).map_err(io_err)
+    }
 
+    /// Decodes a stat, validating that the declared `size[2]` matches the
+    /// number of bytes actually consumed by the fields that follow it, and
+    /// rejecting input truncated before `size` bytes are available.
+    pub fn decode(r: &mut dyn Read) -> Result<Self> {
+        let size = read_u16(r)? as usize;
+        let mut body = vec![0u8; size];
+        r.read_exact(&mut body).map_err(io_err)?;
+        let mut cursor: &[u8] = &body;
+
+        let type_ = read_u16(&mut cursor)?;
+        let dev = read_u32(&mut cursor)?;
+        let qid = Qid::decode(&mut cursor)?;
+        let mode = FileMode::decode(&mut cursor)?;
+        let atime = read_u32(&mut cursor)?;
+        let mtime = read_u32(&mut cursor)?;
+        let length = read_u64(&mut cursor)?;
+        let name = read_string(&mut cursor)?;
+        let uid = read_string(&mut cursor)?;
+        let gid = read_string(&mut cursor)?;
+        let muid = read_string(&mut cursor)?;
+
+        if !cursor.is_empty() { return Err(Vfs9Error::invalid("stat size field does not match the length of the encoded fields")); }
+
+        Ok(Self { type_, dev, qid, mode, atime, mtime, length, name, uid, gid, muid })
+    }
+}
 
 /// A filesystem entity, either a directory or a file.
 pub trait FsEntity {
   fn stat(&self) -> Result<Stat>;
   fn wstat(&self, stat: &Stat) -> Result<()>;
+
+  /// The 9P2000.L (dotL) equivalent of `stat`: returns only the fields
+  /// requested by `mask`, plus the numeric uid/gid, nlink, rdev and block
+  /// counts that classic 9P2000's `Stat` has no room for.
+  fn getattr(&self, mask: &GetAttrMask) -> Result<GetAttr>;
+
+  /// The 9P2000.L (dotL) equivalent of `wstat`: applies only the fields of
+  /// `attr` selected by `mask`.
+  fn setattr(&mut self, mask: &SetAttrMask, attr: &SetAttr) -> Result<()>;
 }
 
 pub trait Directory<F: File>: FsEntity + std::marker::Sized {
@@ -329,3 +804,551 @@ pub enum DirectoryOrFile<F, D> {
     File(F),
     Directory(D),
 }
+
+/// The access mode encoded in the low two bits of a 9P2000.L open/create flags word
+/// (Linux `O_ACCMODE`). Unlike classic 9P's `OpenSubMode`, dotL has no separate
+/// execute mode; execute permission is implied by the file's mode bits instead.
+#[derive(Debug, PartialEq)]
+pub enum LAccessMode {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+}
+
+impl TryFrom<u32> for LAccessMode {
+    type Error = Vfs9Error;
+
+    fn try_from(bits: u32) -> std::result::Result<Self, Self::Error> {
+        let mode: u32 = bits & 0b11;
+        match mode {
+            0 => Ok(Self::ReadOnly),
+            1 => Ok(Self::WriteOnly),
+            2 => Ok(Self::ReadWrite),
+            _ => Err(Vfs9Error::invalid(format!("invalid open access mode bits: {:#04b}", mode)))
+        }
+    }
+}
+
+/// The flags word carried by a 9P2000.L (dotL) `Tlopen`/`Tlcreate` request.
+/// These are the standard Linux `open(2)` flags, as opposed to classic 9P2000's
+/// `OpenMode`, which only has a 2-bit submode plus truncate/rclose.
+#[derive(Debug, PartialEq)]
+pub struct LOpenFlags {
+    pub access: LAccessMode,
+    pub create: bool,
+    pub excl: bool,
+    pub truncate: bool,
+    pub append: bool,
+    pub nonblock: bool,
+    pub direct: bool,
+    pub directory: bool,
+    pub nofollow: bool,
+    pub noatime: bool,
+    pub sync: bool,
+}
+
+const O_CREAT: u32      = 0o000100;
+const O_EXCL: u32       = 0o000200;
+const O_TRUNC: u32      = 0o001000;
+const O_APPEND: u32     = 0o002000;
+const O_NONBLOCK: u32   = 0o004000;
+const O_DIRECT: u32     = 0o040000;
+const O_DIRECTORY: u32  = 0o200000;
+const O_NOFOLLOW: u32   = 0o400000;
+const O_NOATIME: u32    = 0o1000000;
+const O_SYNC: u32       = 0o4000000;
+
+impl LOpenFlags {
+    pub fn from_bits(fields: u32) -> Result<Self> {
+        Ok(Self {
+            access: fields.try_into()?,
+            create: fields & O_CREAT != 0,
+            excl: fields & O_EXCL != 0,
+            truncate: fields & O_TRUNC != 0,
+            append: fields & O_APPEND != 0,
+            nonblock: fields & O_NONBLOCK != 0,
+            direct: fields & O_DIRECT != 0,
+            directory: fields & O_DIRECTORY != 0,
+            nofollow: fields & O_NOFOLLOW != 0,
+            noatime: fields & O_NOATIME != 0,
+            sync: fields & O_SYNC != 0,
+        })
+    }
+
+    pub fn to_bits(&self) -> u32 {
+        let mut b: u32 = match self.access {
+            LAccessMode::ReadOnly => 0,
+            LAccessMode::WriteOnly => 1,
+            LAccessMode::ReadWrite => 2,
+        };
+
+        if self.create    { b |= O_CREAT; }
+        if self.excl       { b |= O_EXCL; }
+        if self.truncate   { b |= O_TRUNC; }
+        if self.append     { b |= O_APPEND; }
+        if self.nonblock   { b |= O_NONBLOCK; }
+        if self.direct     { b |= O_DIRECT; }
+        if self.directory  { b |= O_DIRECTORY; }
+        if self.nofollow   { b |= O_NOFOLLOW; }
+        if self.noatime    { b |= O_NOATIME; }
+        if self.sync       { b |= O_SYNC; }
+
+        b
+    }
+}
+
+/// Selects which fields of a `GetAttr` the caller is interested in, mirroring the
+/// `request_mask`/`valid` bitfield of 9P2000.L's `Tgetattr`/`Rgetattr`.
+#[derive(Debug, PartialEq)]
+pub struct GetAttrMask {
+    pub mode: bool,
+    pub nlink: bool,
+    pub uid: bool,
+    pub gid: bool,
+    pub rdev: bool,
+    pub atime: bool,
+    pub mtime: bool,
+    pub ctime: bool,
+    pub ino: bool,
+    pub size: bool,
+    pub blocks: bool,
+}
+
+const GETATTR_MODE: u64   = 0x00000001;
+const GETATTR_NLINK: u64  = 0x00000002;
+const GETATTR_UID: u64    = 0x00000004;
+const GETATTR_GID: u64    = 0x00000008;
+const GETATTR_RDEV: u64   = 0x00000010;
+const GETATTR_ATIME: u64  = 0x00000020;
+const GETATTR_MTIME: u64  = 0x00000040;
+const GETATTR_CTIME: u64  = 0x00000080;
+const GETATTR_INO: u64    = 0x00000100;
+const GETATTR_SIZE: u64   = 0x00000200;
+const GETATTR_BLOCKS: u64 = 0x00000400;
+
+impl GetAttrMask {
+    pub fn from_bits(fields: u64) -> Self {
+        Self {
+            mode:   fields & GETATTR_MODE   != 0,
+            nlink:  fields & GETATTR_NLINK  != 0,
+            uid:    fields & GETATTR_UID    != 0,
+            gid:    fields & GETATTR_GID    != 0,
+            rdev:   fields & GETATTR_RDEV   != 0,
+            atime:  fields & GETATTR_ATIME  != 0,
+            mtime:  fields & GETATTR_MTIME  != 0,
+            ctime:  fields & GETATTR_CTIME  != 0,
+            ino:    fields & GETATTR_INO    != 0,
+            size:   fields & GETATTR_SIZE   != 0,
+            blocks: fields & GETATTR_BLOCKS != 0,
+        }
+    }
+
+    pub fn to_bits(&self) -> u64 {
+        let mut b: u64 = 0x0000000000000000;
+
+        if self.mode   { b |= GETATTR_MODE;   }
+        if self.nlink  { b |= GETATTR_NLINK;  }
+        if self.uid    { b |= GETATTR_UID;    }
+        if self.gid    { b |= GETATTR_GID;    }
+        if self.rdev   { b |= GETATTR_RDEV;   }
+        if self.atime  { b |= GETATTR_ATIME;  }
+        if self.mtime  { b |= GETATTR_MTIME;  }
+        if self.ctime  { b |= GETATTR_CTIME;  }
+        if self.ino    { b |= GETATTR_INO;    }
+        if self.size   { b |= GETATTR_SIZE;   }
+        if self.blocks { b |= GETATTR_BLOCKS; }
+
+        b
+    }
+}
+
+/// A nanosecond-resolution timestamp, as used throughout 9P2000.L's attribute messages.
+#[derive(Debug, PartialEq)]
+pub struct Timespec {
+    pub sec: u64,
+    pub nsec: u64,
+}
+
+/// The 9P2000.L `Rgetattr` attribute set: the Linux `struct stat64` fields that
+/// classic 9P2000's `Stat` has no room for (numeric uid/gid, nlink, rdev, block
+/// counts, nanosecond timestamps), returned alongside the fields the caller asked
+/// for in the request mask.
+#[derive(Debug, PartialEq)]
+pub struct GetAttr {
+    pub valid: GetAttrMask,
+    pub qid: Qid,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub nlink: u64,
+    pub rdev: u64,
+    pub size: u64,
+    pub blksize: u64,
+    pub blocks: u64,
+    pub atime: Timespec,
+    pub mtime: Timespec,
+    pub ctime: Timespec,
+}
+
+/// Selects which fields of a `SetAttr` the server should apply, mirroring the
+/// `valid` bitfield of 9P2000.L's `Tsetattr`.
+#[derive(Debug, PartialEq)]
+pub struct SetAttrMask {
+    pub mode: bool,
+    pub uid: bool,
+    pub gid: bool,
+    pub size: bool,
+    pub atime: bool,
+    pub mtime: bool,
+
+    /// if set, atime is taken from the request rather than from the current time
+    pub atime_set: bool,
+
+    /// if set, mtime is taken from the request rather than from the current time
+    pub mtime_set: bool,
+}
+
+const SETATTR_MODE: u32      = 0x00000001;
+const SETATTR_UID: u32       = 0x00000002;
+const SETATTR_GID: u32       = 0x00000004;
+const SETATTR_SIZE: u32      = 0x00000008;
+const SETATTR_ATIME: u32     = 0x00000010;
+const SETATTR_MTIME: u32     = 0x00000020;
+const SETATTR_ATIME_SET: u32 = 0x00000080;
+const SETATTR_MTIME_SET: u32 = 0x00000100;
+
+impl SetAttrMask {
+    pub fn from_bits(fields: u32) -> Self {
+        Self {
+            mode:      fields & SETATTR_MODE      != 0,
+            uid:       fields & SETATTR_UID       != 0,
+            gid:       fields & SETATTR_GID       != 0,
+            size:      fields & SETATTR_SIZE      != 0,
+            atime:     fields & SETATTR_ATIME     != 0,
+            mtime:     fields & SETATTR_MTIME     != 0,
+            atime_set: fields & SETATTR_ATIME_SET != 0,
+            mtime_set: fields & SETATTR_MTIME_SET != 0,
+        }
+    }
+
+    pub fn to_bits(&self) -> u32 {
+        let mut b: u32 = 0x00000000;
+
+        if self.mode      { b |= SETATTR_MODE;      }
+        if self.uid       { b |= SETATTR_UID;       }
+        if self.gid       { b |= SETATTR_GID;       }
+        if self.size      { b |= SETATTR_SIZE;      }
+        if self.atime     { b |= SETATTR_ATIME;     }
+        if self.mtime     { b |= SETATTR_MTIME;     }
+        if self.atime_set { b |= SETATTR_ATIME_SET; }
+        if self.mtime_set { b |= SETATTR_MTIME_SET; }
+
+        b
+    }
+}
+
+/// The 9P2000.L `Tsetattr` attribute set, applied according to `SetAttrMask`.
+#[derive(Debug, PartialEq)]
+pub struct SetAttr {
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    pub atime: Timespec,
+    pub mtime: Timespec,
+}
+
+/// The kind of access being requested against a file's permissions,
+/// as checked by `check_permission`.
+#[derive(Debug, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+impl Access {
+    /// The bit weight of this access kind within a single rwx triple
+    /// (R=4, W=2, X=1), matching `Permissions::to_bits`.
+    fn weight(&self) -> u32 {
+        match self {
+            Self::Read => BIT_OTHER_READ,
+            Self::Write => BIT_OTHER_WRITE,
+            Self::Execute => BIT_OTHER_EXECUTE,
+        }
+    }
+}
+
+/// Resolves group membership for `check_permission`. Groups and users are
+/// both named by the strings carried in `Stat`/`FsEntity`, so resolving
+/// membership is left up to the implementor (e.g. backed by /etc/group,
+/// an LDAP lookup, or a static table).
+pub trait GroupResolver {
+    fn is_member(&self, group: &str, user: &str) -> bool;
+}
+
+/// Checks whether `requester` is allowed `want` access to a file with the
+/// given `stat`, following the classic Plan 9 (fossil) permission algorithm:
+///
+/// The special user `none` never gets owner or group bits, only whatever
+/// the "other" triple of `mode` grants. Otherwise, if `requester` is the
+/// file's owner (`stat.uid`, already a name, so no separate uid-to-name
+/// resolution is needed) and the owner triple grants `want`, access is
+/// allowed. Failing that, if `resolver` reports `requester` is a member of
+/// the file's group (`stat.gid`) and the group triple grants `want`, access
+/// is allowed. Otherwise access falls back to the "other" triple, except
+/// that execute permission on a directory is always granted to the world
+/// (every directory must remain traversable).
+pub fn check_permission(stat: &Stat, requester: &str, want: Access, resolver: &dyn GroupResolver) -> Result<()> {
+    let mode = stat.mode.permissions.to_bits();
+    let weight = want.weight();
+
+    if requester != "none" {
+        if requester == stat.uid && (weight << 6) & mode != 0 {
+            return Ok(());
+        }
+
+        if resolver.is_member(&stat.gid, requester) && (weight << 3) & mode != 0 {
+            return Ok(());
+        }
+    }
+
+    if stat.mode.file_type.is_dir() && want == Access::Execute {
+        return Ok(());
+    }
+
+    if weight & mode != 0 {
+        return Ok(());
+    }
+
+    Err(Vfs9Error::perm())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestGroups;
+
+    impl GroupResolver for TestGroups {
+        fn is_member(&self, group: &str, user: &str) -> bool {
+            matches!((group, user), ("staff", "alice") | ("staff", "bob"))
+        }
+    }
+
+    fn stat_with(uid: &str, gid: &str, perm: u32, is_dir: bool) -> Stat {
+        let mut file_type = FileType::empty();
+        if is_dir { file_type.insert(FileType::DIR); }
+
+        Stat {
+            type_: 0,
+            dev: 0,
+            qid: Qid { file_type, version: 0, path: 0 },
+            mode: FileMode { permissions: Permissions::from_bits(perm).unwrap(), file_type },
+            atime: 0,
+            mtime: 0,
+            length: 0,
+            name: "f".to_string(),
+            uid: uid.to_string(),
+            gid: gid.to_string(),
+            muid: uid.to_string(),
+        }
+    }
+
+    #[test]
+    fn owner_gets_access_via_owner_bits() {
+        let stat = stat_with("alice", "staff", 0o600, false);
+        assert!(check_permission(&stat, "alice", Access::Read, &TestGroups).is_ok());
+        assert!(check_permission(&stat, "alice", Access::Write, &TestGroups).is_ok());
+    }
+
+    #[test]
+    fn non_owner_non_member_falls_back_to_other_bits() {
+        let stat = stat_with("alice", "staff", 0o604, false);
+        assert!(check_permission(&stat, "carol", Access::Read, &TestGroups).is_ok());
+        assert!(check_permission(&stat, "carol", Access::Write, &TestGroups).is_err());
+    }
+
+    #[test]
+    fn group_member_gets_access_via_group_bits() {
+        let stat = stat_with("alice", "staff", 0o640, false);
+        assert!(check_permission(&stat, "bob", Access::Read, &TestGroups).is_ok());
+        assert!(check_permission(&stat, "bob", Access::Write, &TestGroups).is_err());
+    }
+
+    #[test]
+    fn none_user_only_ever_gets_other_bits() {
+        let stat = stat_with("none", "staff", 0o700, false);
+        assert!(check_permission(&stat, "none", Access::Read, &TestGroups).is_err());
+    }
+
+    #[test]
+    fn directory_execute_is_always_granted() {
+        let stat = stat_with("alice", "staff", 0o600, true);
+        assert!(check_permission(&stat, "carol", Access::Execute, &TestGroups).is_ok());
+    }
+
+    #[test]
+    fn directory_other_bits_still_apply_to_non_execute_access() {
+        let stat = stat_with("alice", "staff", 0o600, true);
+        assert!(check_permission(&stat, "carol", Access::Read, &TestGroups).is_err());
+    }
+
+    fn sample_stat() -> Stat {
+        Stat {
+            type_: 1,
+            dev: 2,
+            qid: Qid { file_type: FileType::DIR, version: 1, path: 99 },
+            mode: FileMode { permissions: Permissions::from_bits(0o755).unwrap(), file_type: FileType::DIR },
+            atime: 111,
+            mtime: 222,
+            length: 333,
+            name: "root".to_string(),
+            uid: "alice".to_string(),
+            gid: "staff".to_string(),
+            muid: "alice".to_string(),
+        }
+    }
+
+    #[test]
+    fn qid_round_trips_through_the_wire() {
+        let qid = Qid { file_type: FileType::DIR, version: 7, path: 42 };
+        let mut buf = Vec::new();
+        qid.encode(&mut buf).unwrap();
+        assert_eq!(buf.len(), 13);
+
+        let mut cursor = &buf[..];
+        assert_eq!(Qid::decode(&mut cursor).unwrap(), qid);
+    }
+
+    #[test]
+    fn file_mode_round_trips_through_the_wire() {
+        let mode = FileMode { permissions: Permissions::from_bits(0o644).unwrap(), file_type: FileType::DIR };
+        let mut buf = Vec::new();
+        mode.encode(&mut buf).unwrap();
+
+        let mut cursor = &buf[..];
+        assert_eq!(FileMode::decode(&mut cursor).unwrap(), mode);
+    }
+
+    #[test]
+    fn stat_round_trips_through_the_wire() {
+        let stat = sample_stat();
+        let mut buf = Vec::new();
+        stat.encode(&mut buf).unwrap();
+
+        let mut cursor = &buf[..];
+        assert_eq!(Stat::decode(&mut cursor).unwrap(), stat);
+    }
+
+    #[test]
+    fn stat_decode_rejects_truncated_input() {
+        let mut buf = Vec::new();
+        sample_stat().encode(&mut buf).unwrap();
+        buf.truncate(buf.len() - 1);
+
+        let mut cursor = &buf[..];
+        assert!(Stat::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn stat_decode_rejects_size_field_not_matching_consumed_bytes() {
+        let mut buf = Vec::new();
+        sample_stat().encode(&mut buf).unwrap();
+
+        // Claim one more byte than was actually encoded, and pad so the
+        // declared size[2] can still be read in full; decode should notice
+        // the leftover byte rather than silently accepting it.
+        let declared = u16::from_le_bytes([buf[0], buf[1]]);
+        let bumped = (declared + 1).to_le_bytes();
+        buf[0] = bumped[0];
+        buf[1] = bumped[1];
+        buf.push(0);
+
+        let mut cursor = &buf[..];
+        assert!(Stat::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn lopen_flags_round_trip_through_bits() {
+        let flags = LOpenFlags {
+            access: LAccessMode::ReadWrite,
+            create: true,
+            excl: true,
+            truncate: true,
+            append: true,
+            nonblock: true,
+            direct: true,
+            directory: true,
+            nofollow: true,
+            noatime: true,
+            sync: true,
+        };
+
+        let decoded = LOpenFlags::from_bits(flags.to_bits()).unwrap();
+        assert_eq!(decoded, flags);
+    }
+
+    #[test]
+    fn lopen_flags_from_bits_rejects_invalid_access_mode() {
+        // O_ACCMODE (0o3) has no meaning when all of its bits are set.
+        assert!(LOpenFlags::from_bits(0o3).is_err());
+    }
+
+    #[test]
+    fn getattr_mask_round_trips_through_bits() {
+        let mask = GetAttrMask {
+            mode: true,
+            nlink: true,
+            uid: true,
+            gid: true,
+            rdev: true,
+            atime: true,
+            mtime: true,
+            ctime: true,
+            ino: true,
+            size: true,
+            blocks: true,
+        };
+
+        assert_eq!(GetAttrMask::from_bits(mask.to_bits()), mask);
+    }
+
+    #[test]
+    fn setattr_mask_round_trips_through_bits() {
+        let mask = SetAttrMask {
+            mode: true,
+            uid: true,
+            gid: true,
+            size: true,
+            atime: true,
+            mtime: true,
+            atime_set: true,
+            mtime_set: true,
+        };
+
+        assert_eq!(SetAttrMask::from_bits(mask.to_bits()), mask);
+    }
+
+    #[test]
+    fn file_mode_display_renders_symbolic_string() {
+        let mode = FileMode { permissions: Permissions::from_bits(0o664).unwrap(), file_type: FileType::empty() };
+        assert_eq!(mode.to_string(), "-rw-rw-r--");
+    }
+
+    #[test]
+    fn file_mode_octal_matches_unix_st_mode() {
+        let mode = FileMode { permissions: Permissions::from_bits(0o664).unwrap(), file_type: FileType::empty() };
+        assert_eq!(mode.octal(), 0o100664);
+    }
+
+    #[test]
+    fn directory_file_mode_display_and_octal() {
+        let mut file_type = FileType::empty();
+        file_type.insert(FileType::DIR);
+        let mode = FileMode { permissions: Permissions::from_bits(0o755).unwrap(), file_type };
+
+        assert_eq!(mode.to_string(), "drwxr-xr-x");
+        assert_eq!(mode.octal(), 0o040755);
+    }
+}